@@ -0,0 +1,263 @@
+/* This Source Code Form is subject to the terms of the Mozilla Public
+ * License, v. 2.0. If a copy of the MPL was not distributed with this
+ * file, You can obtain one at http://mozilla.org/MPL/2.0/. */
+
+use crate::{
+    command::{CommandBuffer, CommandEncoderError},
+    hub::{GfxBackend, Global, GlobalIdentityHandlerFactory, Token},
+    id,
+    resource::BufferUse,
+    span, Stored,
+};
+
+use hal::{command::CommandBuffer as _, device::Device as _};
+use thiserror::Error;
+
+// This module assumes `id::QuerySetId`, a `hub.query_sets` `Storage` slot, and
+// `wgt::QuerySetDescriptor` already exist, and that some `device_create_query_set`
+// entry point (that would live in `device.rs`) is what produces a `QuerySetId` in the
+// first place. None of those land in this commit, so as shipped here this module has
+// no way to create a `QuerySet` or obtain an id for one; wiring that up is
+// `id`/`hub`/`device` follow-up work, not included.
+//
+// `write_timestamp` and the pipeline-statistics begin/end pair below also only work
+// at the top level of an encoder: recording them as `RenderCommand`/`ComputeCommand`
+// variants so they can be issued from inside a pass is not implemented here either.
+
+/// A set of GPU timestamp or pipeline-statistics queries, resolved in bulk into a
+/// destination buffer via [`Global::command_encoder_resolve_query_set`].
+#[derive(Debug)]
+pub struct QuerySet<B: hal::Backend> {
+    pub(crate) raw: B::QueryPool,
+    pub(crate) device_id: Stored<id::DeviceId>,
+    pub(crate) desc: wgt::QuerySetDescriptor,
+}
+
+#[derive(Clone, Debug, Error)]
+pub enum QueryError {
+    #[error(transparent)]
+    Encoder(#[from] CommandEncoderError),
+    #[error("query set is invalid")]
+    InvalidQuerySet,
+    #[error("query index {index} is out of range for a query set of size {count}")]
+    OutOfRange { index: u32, count: u32 },
+    #[error(transparent)]
+    UsageConflict(#[from] crate::command::UsageConflict),
+    #[error("a pipeline statistics query is already active on this encoder")]
+    StatisticsQueryAlreadyActive,
+    #[error("no pipeline statistics query with this query set and index is active on this encoder")]
+    NoStatisticsQueryActive,
+}
+
+/// Checks `index` against the number of queries `desc` describes, independent of any
+/// `hub`/`Storage` lookup so it can be exercised directly in tests.
+fn validate_query_index(index: u32, desc: &wgt::QuerySetDescriptor) -> Result<(), QueryError> {
+    if index < desc.count {
+        Ok(())
+    } else {
+        Err(QueryError::OutOfRange {
+            index,
+            count: desc.count,
+        })
+    }
+}
+
+/// Size in bytes of the resolved results for `range` of `desc`: one `u64` per query for
+/// `Timestamp` sets, but one `u64` per *enabled statistic* per query for
+/// `PipelineStatistics` sets, since `copy_query_pool_results` writes every enabled
+/// counter back to back for each query index.
+fn resolved_query_size(desc: &wgt::QuerySetDescriptor, range: &std::ops::Range<u32>) -> wgt::BufferAddress {
+    let count = (range.end - range.start) as wgt::BufferAddress;
+    let values_per_query = match desc.ty {
+        wgt::QueryType::Timestamp => 1,
+        wgt::QueryType::PipelineStatistics(flags) => flags.bits().count_ones() as wgt::BufferAddress,
+    };
+    count * values_per_query * std::mem::size_of::<u64>() as wgt::BufferAddress
+}
+
+impl<G: GlobalIdentityHandlerFactory> Global<G> {
+    pub fn command_encoder_write_timestamp<B: GfxBackend>(
+        &self,
+        encoder_id: id::CommandEncoderId,
+        query_set_id: id::QuerySetId,
+        query_index: u32,
+    ) -> Result<(), QueryError> {
+        span!(_guard, DEBUG, "CommandEncoder::write_timestamp");
+
+        let hub = B::hub(self);
+        let mut token = Token::root();
+        let (query_set_guard, mut token) = hub.query_sets.read(&mut token);
+        let (mut cmd_buf_guard, _) = hub.command_buffers.write(&mut token);
+        let cmd_buf = CommandBuffer::get_encoder(&mut *cmd_buf_guard, encoder_id)?;
+        let query_set = query_set_guard
+            .get(query_set_id)
+            .map_err(|_| QueryError::InvalidQuerySet)?;
+        validate_query_index(query_index, &query_set.desc)?;
+
+        let raw = cmd_buf.raw.last_mut().unwrap();
+        unsafe {
+            raw.write_timestamp(hal::pso::PipelineStage::BOTTOM_OF_PIPE, &query_set.raw, query_index);
+        }
+        Ok(())
+    }
+
+    pub fn command_encoder_begin_pipeline_statistics_query<B: GfxBackend>(
+        &self,
+        encoder_id: id::CommandEncoderId,
+        query_set_id: id::QuerySetId,
+        query_index: u32,
+    ) -> Result<(), QueryError> {
+        span!(_guard, DEBUG, "CommandEncoder::begin_pipeline_statistics_query");
+
+        let hub = B::hub(self);
+        let mut token = Token::root();
+        let (query_set_guard, mut token) = hub.query_sets.read(&mut token);
+        let (mut cmd_buf_guard, _) = hub.command_buffers.write(&mut token);
+        let cmd_buf = CommandBuffer::get_encoder(&mut *cmd_buf_guard, encoder_id)?;
+        let query_set = query_set_guard
+            .get(query_set_id)
+            .map_err(|_| QueryError::InvalidQuerySet)?;
+        validate_query_index(query_index, &query_set.desc)?;
+
+        if cmd_buf.active_pipeline_statistics_query.is_some() {
+            return Err(QueryError::StatisticsQueryAlreadyActive);
+        }
+
+        let raw = cmd_buf.raw.last_mut().unwrap();
+        unsafe {
+            raw.begin_query(&query_set.raw, query_index, hal::query::ControlFlags::empty());
+        }
+        cmd_buf.active_pipeline_statistics_query = Some((query_set_id, query_index));
+        Ok(())
+    }
+
+    pub fn command_encoder_end_pipeline_statistics_query<B: GfxBackend>(
+        &self,
+        encoder_id: id::CommandEncoderId,
+        query_set_id: id::QuerySetId,
+        query_index: u32,
+    ) -> Result<(), QueryError> {
+        span!(_guard, DEBUG, "CommandEncoder::end_pipeline_statistics_query");
+
+        let hub = B::hub(self);
+        let mut token = Token::root();
+        let (query_set_guard, mut token) = hub.query_sets.read(&mut token);
+        let (mut cmd_buf_guard, _) = hub.command_buffers.write(&mut token);
+        let cmd_buf = CommandBuffer::get_encoder(&mut *cmd_buf_guard, encoder_id)?;
+        let query_set = query_set_guard
+            .get(query_set_id)
+            .map_err(|_| QueryError::InvalidQuerySet)?;
+        validate_query_index(query_index, &query_set.desc)?;
+
+        if cmd_buf.active_pipeline_statistics_query != Some((query_set_id, query_index)) {
+            return Err(QueryError::NoStatisticsQueryActive);
+        }
+
+        let raw = cmd_buf.raw.last_mut().unwrap();
+        unsafe {
+            raw.end_query(&query_set.raw, query_index);
+        }
+        cmd_buf.active_pipeline_statistics_query = None;
+        Ok(())
+    }
+
+    /// Copies the resolved results of `query_range` from `query_set_id` into
+    /// `destination`, which can then be mapped back like the destination of any other
+    /// transfer.
+    ///
+    /// `use_replace` only resolves a barrier for the one buffer it's touching rather
+    /// than merging two whole `TrackerSet`s, so this records it directly with
+    /// `pipeline_barrier` instead of going through `CommandBuffer::insert_barriers`,
+    /// which expects a full `base`/`head` pair.
+    pub fn command_encoder_resolve_query_set<B: GfxBackend>(
+        &self,
+        encoder_id: id::CommandEncoderId,
+        query_set_id: id::QuerySetId,
+        query_range: std::ops::Range<u32>,
+        destination: id::BufferId,
+        destination_offset: wgt::BufferAddress,
+    ) -> Result<(), QueryError> {
+        span!(_guard, DEBUG, "CommandEncoder::resolve_query_set");
+
+        let hub = B::hub(self);
+        let mut token = Token::root();
+        let (query_set_guard, mut token) = hub.query_sets.read(&mut token);
+        let (buffer_guard, mut token) = hub.buffers.read(&mut token);
+        let (mut cmd_buf_guard, _) = hub.command_buffers.write(&mut token);
+        let cmd_buf = CommandBuffer::get_encoder(&mut *cmd_buf_guard, encoder_id)?;
+        let query_set = query_set_guard
+            .get(query_set_id)
+            .map_err(|_| QueryError::InvalidQuerySet)?;
+
+        if query_range.end > query_set.desc.count {
+            return Err(QueryError::OutOfRange {
+                index: query_range.end,
+                count: query_set.desc.count,
+            });
+        }
+
+        let dst_barrier = cmd_buf
+            .trackers
+            .buffers
+            .use_replace(&*buffer_guard, destination, (), BufferUse::COPY_DST)?;
+
+        let raw = cmd_buf.raw.last_mut().unwrap();
+        unsafe {
+            raw.pipeline_barrier(
+                hal::pso::PipelineStage::TRANSFER..hal::pso::PipelineStage::TRANSFER,
+                hal::memory::Dependencies::empty(),
+                dst_barrier.map(|pending| pending.into_hal()),
+            );
+            raw.copy_query_pool_results(
+                &query_set.raw,
+                query_range.clone(),
+                &buffer_guard[destination].raw,
+                destination_offset,
+                resolved_query_size(&query_set.desc, &query_range),
+                hal::query::ResultFlags::WAIT | hal::query::ResultFlags::BITS_64,
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn desc(ty: wgt::QueryType, count: u32) -> wgt::QuerySetDescriptor {
+        wgt::QuerySetDescriptor {
+            label: None,
+            ty,
+            count,
+        }
+    }
+
+    #[test]
+    fn validate_query_index_in_range() {
+        assert!(validate_query_index(3, &desc(wgt::QueryType::Timestamp, 4)).is_ok());
+    }
+
+    #[test]
+    fn validate_query_index_out_of_range() {
+        let err = validate_query_index(4, &desc(wgt::QueryType::Timestamp, 4)).unwrap_err();
+        assert!(matches!(
+            err,
+            QueryError::OutOfRange { index: 4, count: 4 }
+        ));
+    }
+
+    #[test]
+    fn resolved_query_size_timestamp_is_one_u64_per_query() {
+        let d = desc(wgt::QueryType::Timestamp, 8);
+        assert_eq!(resolved_query_size(&d, &(2..5)), 3 * 8);
+    }
+
+    #[test]
+    fn resolved_query_size_pipeline_statistics_scales_with_enabled_counters() {
+        let flags = wgt::PipelineStatisticsTypes::CLIPPER_INVOCATIONS
+            | wgt::PipelineStatisticsTypes::CLIPPER_PRIMITIVES_OUT;
+        let d = desc(wgt::QueryType::PipelineStatistics(flags), 8);
+        assert_eq!(resolved_query_size(&d, &(0..2)), 2 * 2 * 8);
+    }
+}